@@ -0,0 +1,414 @@
+//! An async sibling of [`CommandPipeline`](crate::CommandPipeline)/[`Pipeline`](crate::Pipeline),
+//! backed by `tokio::process` instead of `std::process`. Mirrors the same builder API, but
+//! `spawn`/`join`/`join_with_output` are `async fn`s so a stage's `wait` doesn't block the calling
+//! thread, and multiple pipelines can be driven concurrently on one runtime.
+
+use std::{
+    ffi::OsStr,
+    path::Path,
+    process::{CommandArgs, CommandEnvs, ExitStatus, Output, Stdio},
+    time::Duration,
+};
+
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
+
+use crate::OnDrop;
+
+#[derive(Debug)]
+struct AsyncCommandPipelineConfig {
+    pipefail: bool,
+    on_drop: OnDrop,
+    stdin: Option<Stdio>,
+    stdout: Option<Stdio>,
+}
+
+#[derive(Debug)]
+struct AsyncPipelineConfig {
+    pipefail: bool,
+    on_drop: OnDrop,
+}
+
+#[derive(Debug)]
+pub struct AsyncCommandPipeline {
+    piped_commands: Vec<Command>,
+    tail_command: Command,
+    config: AsyncCommandPipelineConfig,
+}
+
+#[derive(Debug)]
+pub struct AsyncPipeline {
+    piped_processes: Vec<Child>,
+    // Not actually optional. This is needed because it implements drop.
+    tail_process: Option<Child>,
+    config: AsyncPipelineConfig,
+
+    pub stdin: Option<ChildStdin>,
+    pub stdout: Option<ChildStdout>,
+    pub stderr: Option<ChildStderr>,
+}
+
+impl AsyncCommandPipelineConfig {
+    pub fn new() -> Self {
+        Self {
+            pipefail: false,
+            on_drop: OnDrop::Wait,
+            stdin: None,
+            stdout: None,
+        }
+    }
+}
+
+impl Default for AsyncCommandPipelineConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsyncCommandPipeline {
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
+        Self {
+            piped_commands: Vec::new(),
+            tail_command: Command::new(program),
+            config: AsyncCommandPipelineConfig::new(),
+        }
+    }
+
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Self {
+        self.tail_command.arg(arg.as_ref());
+        self
+    }
+
+    pub fn args<I, S>(&mut self, args: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.tail_command.args(args);
+        self
+    }
+
+    pub fn env<K, V>(&mut self, key: K, val: V) -> &mut Self
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.tail_command.env(key, val);
+        self
+    }
+
+    pub fn envs<I, K, V>(&mut self, vars: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.tail_command.envs(vars);
+        self
+    }
+
+    pub fn env_remove<K: AsRef<OsStr>>(&mut self, key: K) -> &mut Self {
+        self.tail_command.env_remove(key);
+        self
+    }
+
+    pub fn env_clear(&mut self) -> &mut Self {
+        self.tail_command.env_clear();
+        self
+    }
+
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.tail_command.current_dir(dir);
+        self
+    }
+
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.config.stdin = Some(cfg.into());
+        self
+    }
+
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.config.stdout = Some(cfg.into());
+        self
+    }
+
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Self {
+        self.tail_command.stderr(cfg);
+        self
+    }
+
+    pub fn on_drop(&mut self, cfg: OnDrop) -> &mut Self {
+        self.config.on_drop = cfg;
+        self
+    }
+
+    /// When set, [`AsyncPipeline::join`]/[`AsyncPipeline::join_with_output`] resolve to the
+    /// first non-success exit status in the pipeline instead of always the tail's, mirroring
+    /// shell's `set -o pipefail`.
+    pub fn pipefail(&mut self, cfg: bool) -> &mut Self {
+        self.config.pipefail = cfg;
+        self
+    }
+
+    pub fn pipe<S: AsRef<OsStr>>(&mut self, program: S) -> &mut Self {
+        let command = std::mem::replace(&mut self.tail_command, Command::new(program));
+        self.piped_commands.push(command);
+        self
+    }
+
+    pub async fn spawn(&mut self) -> std::io::Result<AsyncPipeline> {
+        async fn post_error_wait_all(processes: Vec<Child>) {
+            for mut process in processes {
+                let _ = process.wait().await;
+            }
+        }
+
+        if let Some(stdin) = self.config.stdin.take() {
+            self.piped_commands
+                .first_mut()
+                .unwrap_or(&mut self.tail_command)
+                .stdin(stdin);
+        }
+
+        let mut last_stdout = None;
+        let mut piped_processes = Vec::with_capacity(self.piped_commands.len());
+        let mut try_spawn_commands = async || {
+            for command in &mut *self.piped_commands {
+                // Note: this will NEVER match on the first iteration. So it won't override the
+                // stdin we set from the configuration.
+                if let Some(last_stdout) = last_stdout.take() {
+                    command.stdin(TryInto::<Stdio>::try_into(last_stdout)?);
+                }
+                command.stdout(Stdio::piped());
+                let mut process = command.spawn()?;
+                last_stdout = process.stdout.take();
+                piped_processes.push(process);
+            }
+            Ok(())
+        };
+        if let Err(error) = try_spawn_commands().await {
+            drop(last_stdout);
+            post_error_wait_all(piped_processes).await;
+            return Err(error);
+        }
+
+        if let Some(last_stdout) = last_stdout.take() {
+            self.tail_command
+                .stdin(TryInto::<Stdio>::try_into(last_stdout)?);
+        }
+        if let Some(stdout) = self.config.stdout.take() {
+            self.tail_command.stdout(stdout);
+        }
+        match self.tail_command.spawn() {
+            Ok(mut tail_process) => {
+                let stdin = piped_processes
+                    .first_mut()
+                    .unwrap_or(&mut tail_process)
+                    .stdin
+                    .take();
+                let stdout = tail_process.stdout.take();
+                let stderr = tail_process.stderr.take();
+                Ok(AsyncPipeline {
+                    piped_processes,
+                    tail_process: Some(tail_process),
+                    config: AsyncPipelineConfig {
+                        pipefail: self.config.pipefail,
+                        on_drop: self.config.on_drop,
+                    },
+                    stdin,
+                    stdout,
+                    stderr,
+                })
+            }
+            Err(error) => {
+                post_error_wait_all(piped_processes).await;
+                Err(error)
+            }
+        }
+    }
+
+    pub async fn output(&mut self) -> std::io::Result<Output> {
+        self.spawn().await?.join_with_output().await
+    }
+
+    pub async fn status(&mut self) -> std::io::Result<ExitStatus> {
+        self.spawn().await?.join().await
+    }
+
+    pub fn get_program(&self) -> &OsStr {
+        self.tail_command.as_std().get_program()
+    }
+
+    pub fn get_args(&self) -> CommandArgs<'_> {
+        self.tail_command.as_std().get_args()
+    }
+
+    pub fn get_envs(&self) -> CommandEnvs<'_> {
+        self.tail_command.as_std().get_envs()
+    }
+
+    pub fn get_current_dir(&self) -> Option<&Path> {
+        self.tail_command.as_std().get_current_dir()
+    }
+}
+
+impl AsyncPipeline {
+    pub async fn join(mut self) -> std::io::Result<ExitStatus> {
+        // Wait for each process in the pipeline, collecting the first exit status that is not a
+        // success if the pipefail flag is enabled.
+        let mut first_err_status = None;
+        for process in &mut self.piped_processes {
+            let result = process.wait().await;
+            if self.config.pipefail
+                && first_err_status.is_none()
+                && !result
+                    .as_ref()
+                    .is_ok_and(|exit_status| exit_status.success())
+            {
+                first_err_status = Some(result);
+            }
+        }
+        let tail_status = self.tail_process.take().unwrap().wait().await;
+        // We've already waited on all the exit codes. No reason to do it again on Drop.
+        self.config.on_drop = OnDrop::Forget;
+
+        if self.config.pipefail
+            && let Some(err_status) = first_err_status
+        {
+            // We delayed returning IO errors until we finished waiting for all the processes. Now,
+            // we need to return the first IO error encountered.
+            let err_status = err_status?;
+            let _ = tail_status?;
+            Ok(err_status)
+        } else {
+            tail_status
+        }
+    }
+
+    pub async fn join_with_output(mut self) -> std::io::Result<Output> {
+        // Wait for each process in the pipeline, collecting the first exit status that is not a
+        // success if the pipefail flag is enabled.
+        let mut first_err_status = None;
+        for process in &mut self.piped_processes {
+            let result = process.wait().await;
+            if self.config.pipefail
+                && first_err_status.is_none()
+                && !result
+                    .as_ref()
+                    .is_ok_and(|exit_status| exit_status.success())
+            {
+                first_err_status = Some(result);
+            }
+        }
+        self.tail_process.as_mut().unwrap().stdout = self.stdout.take();
+        let output = self.tail_process.take().unwrap().wait_with_output().await;
+        // We've already waited on all the exit codes. No reason to do it again on Drop.
+        self.config.on_drop = OnDrop::Forget;
+
+        if self.config.pipefail
+            && let Some(err_status) = first_err_status
+        {
+            // We delayed returning IO errors until we finished waiting for all the processes. Now,
+            // we need to return the first IO error encountered.
+            let err_status = err_status?;
+            let mut output = output?;
+            output.status = err_status;
+            Ok(output)
+        } else {
+            output
+        }
+    }
+}
+
+impl Drop for AsyncPipeline {
+    fn drop(&mut self) {
+        // `Drop` can't be `async`, so unlike `Pipeline::drop` we can't block the current thread
+        // on `wait`. Instead, hand the processes off to the ambient Tokio runtime (if any) to be
+        // reaped in the background; callers that need the exit statuses should call `join`
+        // instead of letting the pipeline drop.
+        let on_drop = self.config.on_drop;
+        if matches!(on_drop, OnDrop::Forget) {
+            return;
+        }
+        let mut processes = std::mem::take(&mut self.piped_processes);
+        if let Some(tail) = self.tail_process.take() {
+            processes.push(tail);
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        handle.spawn(async move {
+            match on_drop {
+                OnDrop::Kill => {
+                    for process in &mut processes {
+                        let _ = process.start_kill();
+                    }
+                    for mut process in processes {
+                        let _ = process.wait().await;
+                    }
+                }
+                OnDrop::TerminateThen(timeout) => {
+                    terminate_then_kill(processes, timeout).await;
+                }
+                OnDrop::Wait | OnDrop::Forget => {
+                    for mut process in processes {
+                        let _ = process.wait().await;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Async equivalent of `crate::terminate_then_kill`: sends a polite termination request to every
+/// process, polls them with `try_wait` until `timeout` elapses, then hard-kills whichever are
+/// still alive. Uses `tokio::time::sleep` instead of blocking the thread, since this runs inside
+/// a spawned task rather than on `Drop`'s calling thread.
+async fn terminate_then_kill(mut processes: Vec<Child>, timeout: Duration) {
+    for process in &mut processes {
+        let _ = send_terminate(process);
+    }
+
+    let deadline = tokio::time::Instant::now() + timeout;
+    const POLL_INTERVAL: Duration = Duration::from_millis(20);
+    loop {
+        let mut index = 0;
+        while index < processes.len() {
+            match processes[index].try_wait() {
+                Ok(Some(_)) => {
+                    processes.swap_remove(index);
+                }
+                _ => index += 1,
+            }
+        }
+        if processes.is_empty() {
+            return;
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL.min(deadline - now)).await;
+    }
+
+    for mut process in processes {
+        let _ = process.start_kill();
+        let _ = process.wait().await;
+    }
+}
+
+#[cfg(unix)]
+fn send_terminate(process: &mut Child) -> std::io::Result<()> {
+    let Some(pid) = process.id() else {
+        return Ok(());
+    };
+    nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(pid as libc::pid_t),
+        nix::sys::signal::Signal::SIGTERM,
+    )
+    .map_err(std::io::Error::from)
+}
+
+#[cfg(not(unix))]
+fn send_terminate(process: &mut Child) -> std::io::Result<()> {
+    process.start_kill()
+}
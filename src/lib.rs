@@ -1,36 +1,133 @@
 use std::{
     ffi::OsStr,
+    fmt,
+    io::Read,
     path::Path,
     process::{
         Child, ChildStderr, ChildStdin, ChildStdout, Command, CommandArgs, CommandEnvs, ExitStatus,
         Output, Stdio,
     },
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
 };
 
+use serde::{Deserialize, Serialize};
+
+pub mod async_pipeline;
+pub mod sequence;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum OnDrop {
     Forget,
     Wait,
     Kill,
+    /// Sends every process a polite termination request (`SIGTERM` on Unix, `kill` on Windows),
+    /// waits up to the given [`Duration`] for them to exit on their own, and hard-kills whichever
+    /// ones are still alive once the deadline passes.
+    TerminateThen(Duration),
+}
+
+type EventCallback = Arc<Mutex<dyn FnMut(PipelineEvent) + Send>>;
+
+/// A serializable snapshot of a [`std::process::ExitStatus`], carried by [`PipelineEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitStatusInfo {
+    /// The process's exit code, or `None` if it was terminated by a signal (Unix only).
+    pub code: Option<i32>,
+    pub success: bool,
+}
+
+impl From<ExitStatus> for ExitStatusInfo {
+    fn from(status: ExitStatus) -> Self {
+        Self {
+            code: status.code(),
+            success: status.success(),
+        }
+    }
+}
+
+/// A lifecycle event fired by a [`Pipeline`] as it runs, for callers that want to log or forward
+/// progress (e.g. over a channel to a UI) instead of polling `try_wait` themselves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PipelineEvent {
+    /// Stage `index` (0 = head, counting up to the tail stage) was spawned.
+    StageSpawned { index: usize, pid: u32 },
+    /// Stage `index` exited and has been reaped.
+    StageExited {
+        index: usize,
+        status: ExitStatusInfo,
+    },
+    /// Every stage has exited; `status` is the overall pipeline status (see [`Pipeline::join`]).
+    PipelineFinished { status: ExitStatusInfo },
+}
+
+fn fire_event(on_event: &Option<EventCallback>, event: PipelineEvent) {
+    if let Some(on_event) = on_event
+        && let Ok(mut on_event) = on_event.lock()
+    {
+        on_event(event);
+    }
 }
 
-#[derive(Debug)]
 struct CommandPipelineConfig {
     pipefail: bool,
     on_drop: OnDrop,
     stdin: Option<Stdio>,
     stdout: Option<Stdio>,
+    on_event: Option<EventCallback>,
+}
+
+impl fmt::Debug for CommandPipelineConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CommandPipelineConfig")
+            .field("pipefail", &self.pipefail)
+            .field("on_drop", &self.on_drop)
+            .field("stdin", &self.stdin)
+            .field("stdout", &self.stdout)
+            .field("on_event", &self.on_event.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
-#[derive(Debug)]
 struct PipelineConfig {
     pipefail: bool,
     on_drop: OnDrop,
+    on_event: Option<EventCallback>,
+}
+
+impl fmt::Debug for PipelineConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineConfig")
+            .field("pipefail", &self.pipefail)
+            .field("on_drop", &self.on_drop)
+            .field("on_event", &self.on_event.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// How a stage's output is fed into the next stage's stdin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum StageForward {
+    /// The stage's stdout becomes the next stage's stdin (the default).
+    #[default]
+    Stdout,
+    /// The stage's stderr is duped onto its stdout before being handed to the next stage, i.e.
+    /// shell's `2>&1 |`.
+    MergedWithStderr,
+    /// The stage's stderr (instead of its stdout) becomes the next stage's stdin.
+    StderrOnly,
 }
 
 #[derive(Debug)]
 pub struct CommandPipeline {
     piped_commands: Vec<Command>,
+    // How each entry in `piped_commands` forwards its output to the next stage, in lockstep with
+    // `piped_commands`.
+    stage_forwards: Vec<StageForward>,
+    // The forwarding mode `tail_command` will be given once it's pushed into `piped_commands` by
+    // `pipe`/`pipe_merged`.
+    pending_forward: StageForward,
     tail_command: Command,
     config: CommandPipelineConfig,
 }
@@ -41,12 +138,31 @@ pub struct Pipeline {
     // Not actually optional. This is needed because it implements drop.
     tail_process: Option<Child>,
     config: PipelineConfig,
+    // Stderr of each piped (non-tail) stage, in pipeline order, captured at spawn time when the
+    // corresponding command was configured with `Stdio::piped()`.
+    piped_stderr: Vec<Option<ChildStderr>>,
+    // The process group id shared by every stage in the pipeline. See `spawn`'s `pre_exec` hooks.
+    #[cfg(unix)]
+    pgid: libc::pid_t,
 
     pub stdin: Option<ChildStdin>,
     pub stdout: Option<ChildStdout>,
     pub stderr: Option<ChildStderr>,
 }
 
+/// The outcome of waiting on a single stage of a [`Pipeline`], as returned by
+/// [`Pipeline::join_all_with_output`].
+#[derive(Debug)]
+pub struct StageOutput {
+    pub status: ExitStatus,
+    /// The stage's captured stdout, or empty for every stage except the tail (intermediate
+    /// stages have their stdout piped into the next stage instead of captured).
+    pub stdout: Vec<u8>,
+    /// The stage's captured stderr, or empty if the stage's command wasn't configured with
+    /// `Stdio::piped()` for stderr.
+    pub stderr: Vec<u8>,
+}
+
 impl CommandPipelineConfig {
     pub fn new() -> Self {
         Self {
@@ -54,6 +170,7 @@ impl CommandPipelineConfig {
             on_drop: OnDrop::Wait,
             stdin: None,
             stdout: None,
+            on_event: None,
         }
     }
 }
@@ -68,6 +185,8 @@ impl CommandPipeline {
     pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
         Self {
             piped_commands: Vec::new(),
+            stage_forwards: Vec::new(),
+            pending_forward: StageForward::default(),
             tail_command: Command::new(program),
             config: CommandPipelineConfig::new(),
         }
@@ -141,9 +260,50 @@ impl CommandPipeline {
         self
     }
 
+    /// When set, [`Pipeline::join`]/[`Pipeline::join_with_output`] resolve to the first
+    /// non-success exit status in the pipeline instead of always the tail's, mirroring shell's
+    /// `set -o pipefail`.
+    pub fn pipefail(&mut self, cfg: bool) -> &mut Self {
+        self.config.pipefail = cfg;
+        self
+    }
+
+    /// Registers a callback fired with a [`PipelineEvent`] at each lifecycle transition
+    /// (`StageSpawned` from `spawn`, `StageExited` from `join`/`join_with_output`/`join_all`/
+    /// `join_all_with_output`/`Drop`, and `PipelineFinished` from those `join*` methods only;
+    /// `Drop` does not fire `PipelineFinished`). The same callback is shared by every [`Pipeline`] this
+    /// `CommandPipeline` spawns.
+    pub fn on_event<F>(&mut self, callback: F) -> &mut Self
+    where
+        F: FnMut(PipelineEvent) + Send + 'static,
+    {
+        self.config.on_event = Some(Arc::new(Mutex::new(callback)));
+        self
+    }
+
     pub fn pipe<S: AsRef<OsStr>>(&mut self, program: S) -> &mut Self {
         let command = std::mem::replace(&mut self.tail_command, Command::new(program));
         self.piped_commands.push(command);
+        self.stage_forwards
+            .push(std::mem::take(&mut self.pending_forward));
+        self
+    }
+
+    /// Like [`pipe`](Self::pipe), but also merges the current stage's stderr into the stream fed
+    /// to `program`'s stdin, i.e. shell's `cmd 2>&1 | program`.
+    ///
+    /// Unix-only: the merge is implemented with a `pre_exec` hook that `dup2`s the child's
+    /// stdout onto its stderr, which has no portable equivalent on Windows.
+    #[cfg(unix)]
+    pub fn pipe_merged<S: AsRef<OsStr>>(&mut self, program: S) -> &mut Self {
+        self.pending_forward = StageForward::MergedWithStderr;
+        self.pipe(program)
+    }
+
+    /// Marks the current stage so that, once it's piped into the next stage with
+    /// [`pipe`](Self::pipe), its stderr (instead of its stdout) becomes the next stage's stdin.
+    pub fn redirect_stderr_to_next(&mut self) -> &mut Self {
+        self.pending_forward = StageForward::StderrOnly;
         self
     }
 
@@ -161,36 +321,87 @@ impl CommandPipeline {
                 .stdin(stdin);
         }
 
-        let mut last_stdout = None;
+        // Every stage joins one process group, so that a caller can suspend/resume/interrupt the
+        // whole pipeline in one call (see `Pipeline::signal`). The first process spawned becomes
+        // the group leader (`setpgid(0, 0)`); every later stage joins that leader's group.
+        #[cfg(unix)]
+        let mut leader_pid: Option<libc::pid_t> = None;
+
+        let on_event = &self.config.on_event;
+        let mut last_stdio: Option<Stdio> = None;
         let mut piped_processes = Vec::with_capacity(self.piped_commands.len());
+        let mut piped_stderr = Vec::with_capacity(self.piped_commands.len());
         let mut try_spawn_commands = || {
-            for command in &mut *self.piped_commands {
+            for (index, (command, &forward)) in self
+                .piped_commands
+                .iter_mut()
+                .zip(&self.stage_forwards)
+                .enumerate()
+            {
                 // Note: this will NEVER match on the first iteration. So it won't override the
                 // stdin we set from the configuration.
-                if let Some(last_stdout) = last_stdout.take() {
-                    command.stdin(last_stdout);
+                if let Some(last_stdio) = last_stdio.take() {
+                    command.stdin(last_stdio);
                 }
-                command.stdout(Stdio::piped());
+                match forward {
+                    StageForward::Stdout => {
+                        command.stdout(Stdio::piped());
+                    }
+                    StageForward::MergedWithStderr => {
+                        command.stdout(Stdio::piped());
+                        #[cfg(unix)]
+                        merge_stderr_into_stdout_pre_exec(command);
+                    }
+                    StageForward::StderrOnly => {
+                        command.stderr(Stdio::piped());
+                    }
+                }
+                #[cfg(unix)]
+                set_pgid_pre_exec(command, leader_pid);
                 let mut process = command.spawn()?;
-                last_stdout = process.stdout.take();
+                #[cfg(unix)]
+                leader_pid.get_or_insert(process.id() as libc::pid_t);
+                fire_event(
+                    on_event,
+                    PipelineEvent::StageSpawned {
+                        index,
+                        pid: process.id(),
+                    },
+                );
+                if forward == StageForward::StderrOnly {
+                    last_stdio = process.stderr.take().map(Stdio::from);
+                    piped_stderr.push(None);
+                } else {
+                    last_stdio = process.stdout.take().map(Stdio::from);
+                    piped_stderr.push(process.stderr.take());
+                }
                 piped_processes.push(process);
             }
             Ok(())
         };
         if let Err(error) = try_spawn_commands() {
-            drop(last_stdout);
+            drop(last_stdio);
             post_error_wait_all(piped_processes);
             return Err(error);
         }
 
-        if let Some(last_stdout) = last_stdout.take() {
-            self.tail_command.stdin(last_stdout);
+        if let Some(last_stdio) = last_stdio.take() {
+            self.tail_command.stdin(last_stdio);
         }
         if let Some(stdout) = self.config.stdout.take() {
             self.tail_command.stdout(stdout);
         }
+        #[cfg(unix)]
+        set_pgid_pre_exec(&mut self.tail_command, leader_pid);
         match self.tail_command.spawn() {
             Ok(mut tail_process) => {
+                fire_event(
+                    on_event,
+                    PipelineEvent::StageSpawned {
+                        index: piped_processes.len(),
+                        pid: tail_process.id(),
+                    },
+                );
                 let stdin = piped_processes
                     .first_mut()
                     .unwrap_or(&mut tail_process)
@@ -199,12 +410,16 @@ impl CommandPipeline {
                 let stdout = tail_process.stdout.take();
                 let stderr = tail_process.stderr.take();
                 Ok(Pipeline {
+                    #[cfg(unix)]
+                    pgid: leader_pid.unwrap_or(tail_process.id() as libc::pid_t),
                     piped_processes,
                     tail_process: Some(tail_process),
                     config: PipelineConfig {
                         pipefail: self.config.pipefail,
                         on_drop: self.config.on_drop,
+                        on_event: self.config.on_event.clone(),
                     },
+                    piped_stderr,
                     stdin,
                     stdout,
                     stderr,
@@ -247,8 +462,17 @@ impl Pipeline {
         // Wait for each process in the pipeline, collecting the first exit status that is not a
         // success if the pipefail flag is enabled.
         let mut first_err_status = None;
-        for process in &mut self.piped_processes {
+        for (index, process) in self.piped_processes.iter_mut().enumerate() {
             let result = process.wait();
+            if let Ok(status) = &result {
+                fire_event(
+                    &self.config.on_event,
+                    PipelineEvent::StageExited {
+                        index,
+                        status: (*status).into(),
+                    },
+                );
+            }
             if self.config.pipefail
                 && first_err_status.is_none()
                 && !result
@@ -258,11 +482,21 @@ impl Pipeline {
                 first_err_status = Some(result);
             }
         }
+        let tail_index = self.piped_processes.len();
         let tail_status = self.tail_process.take().unwrap().wait();
+        if let Ok(status) = &tail_status {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::StageExited {
+                    index: tail_index,
+                    status: (*status).into(),
+                },
+            );
+        }
         // We've already waited on all the exit codes. No reason to do it again on Drop.
         self.config.on_drop = OnDrop::Forget;
 
-        if self.config.pipefail
+        let result = if self.config.pipefail
             && let Some(err_status) = first_err_status
         {
             // We delayed returning IO errors until we finished waiting for all the processes. Now,
@@ -272,15 +506,33 @@ impl Pipeline {
             Ok(err_status)
         } else {
             tail_status
+        };
+        if let Ok(status) = &result {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::PipelineFinished {
+                    status: (*status).into(),
+                },
+            );
         }
+        result
     }
 
     pub fn join_with_output(mut self) -> std::io::Result<Output> {
         // Wait for each process in the pipeline, collecting the first exit status that is not a
         // success if the pipefail flag is enabled.
         let mut first_err_status = None;
-        for process in &mut self.piped_processes {
+        for (index, process) in self.piped_processes.iter_mut().enumerate() {
             let result = process.wait();
+            if let Ok(status) = &result {
+                fire_event(
+                    &self.config.on_event,
+                    PipelineEvent::StageExited {
+                        index,
+                        status: (*status).into(),
+                    },
+                );
+            }
             if self.config.pipefail
                 && first_err_status.is_none()
                 && !result
@@ -291,11 +543,21 @@ impl Pipeline {
             }
         }
         self.tail_process.as_mut().unwrap().stdout = self.stdout.take();
+        let tail_index = self.piped_processes.len();
         let output = self.tail_process.take().unwrap().wait_with_output();
+        if let Ok(output) = &output {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::StageExited {
+                    index: tail_index,
+                    status: output.status.into(),
+                },
+            );
+        }
         // We've already waited on all the exit codes. No reason to do it again on Drop.
         self.config.on_drop = OnDrop::Forget;
 
-        if self.config.pipefail
+        let result = if self.config.pipefail
             && let Some(err_status) = first_err_status
         {
             // We delayed returning IO errors until we finished waiting for all the processes. Now,
@@ -306,7 +568,168 @@ impl Pipeline {
             Ok(output)
         } else {
             output
+        };
+        if let Ok(output) = &result {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::PipelineFinished {
+                    status: output.status.into(),
+                },
+            );
+        }
+        result
+    }
+
+    /// Waits for every stage in the pipeline, returning each stage's exit status in pipeline
+    /// order (head stage first, tail last), regardless of `pipefail`. Unlike [`Pipeline::join`],
+    /// this never collapses the statuses down to a single one, so callers can see exactly which
+    /// stage(s) failed.
+    pub fn join_all(mut self) -> Vec<std::io::Result<ExitStatus>> {
+        let mut statuses = Vec::with_capacity(self.piped_processes.len() + 1);
+        for (index, process) in self.piped_processes.iter_mut().enumerate() {
+            let status = process.wait();
+            if let Ok(status) = &status {
+                fire_event(
+                    &self.config.on_event,
+                    PipelineEvent::StageExited {
+                        index,
+                        status: (*status).into(),
+                    },
+                );
+            }
+            statuses.push(status);
+        }
+        let tail_index = self.piped_processes.len();
+        let tail_status = self.tail_process.take().unwrap().wait();
+        if let Ok(status) = &tail_status {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::StageExited {
+                    index: tail_index,
+                    status: (*status).into(),
+                },
+            );
+        }
+        statuses.push(tail_status);
+        // We've already waited on all the exit codes. No reason to do it again on Drop.
+        self.config.on_drop = OnDrop::Forget;
+
+        // `PipelineFinished` reports the same overall status `join` would have resolved to, so
+        // the event means the same thing regardless of which method produced it.
+        let final_status = if self.config.pipefail {
+            statuses
+                .iter()
+                .find(|result| !result.as_ref().is_ok_and(ExitStatus::success))
+                .or_else(|| statuses.last())
+        } else {
+            statuses.last()
+        };
+        if let Some(Ok(status)) = final_status {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::PipelineFinished {
+                    status: (*status).into(),
+                },
+            );
+        }
+        statuses
+    }
+
+    /// Like [`Pipeline::join_all`], but also captures each stage's stderr (for stages whose
+    /// command was configured with `Stdio::piped()` stderr) and the tail's stdout. Every pipe is
+    /// drained concurrently on its own thread, the same way `std::process::Child::wait_with_output`
+    /// does internally, so a stage that fills its pipe buffer can't deadlock another stage whose
+    /// pipe isn't being read yet.
+    pub fn join_all_with_output(mut self) -> Vec<std::io::Result<StageOutput>> {
+        fn spawn_reader<R>(pipe: Option<R>) -> Option<thread::JoinHandle<Vec<u8>>>
+        where
+            R: Read + Send + 'static,
+        {
+            pipe.map(|mut pipe| {
+                thread::spawn(move || {
+                    let mut buf = Vec::new();
+                    let _ = pipe.read_to_end(&mut buf);
+                    buf
+                })
+            })
+        }
+
+        fn join_reader(reader: Option<thread::JoinHandle<Vec<u8>>>) -> Vec<u8> {
+            reader.and_then(|handle| handle.join().ok()).unwrap_or_default()
+        }
+
+        let piped_stderr_readers: Vec<_> = self
+            .piped_stderr
+            .iter_mut()
+            .map(|stderr| spawn_reader(stderr.take()))
+            .collect();
+        let tail_stdout_reader = spawn_reader(self.stdout.take());
+        let tail_stderr_reader = spawn_reader(self.stderr.take());
+
+        let mut outputs = Vec::with_capacity(self.piped_processes.len() + 1);
+        for (index, (process, stderr_reader)) in self
+            .piped_processes
+            .iter_mut()
+            .zip(piped_stderr_readers)
+            .enumerate()
+        {
+            let status = process.wait();
+            if let Ok(status) = &status {
+                fire_event(
+                    &self.config.on_event,
+                    PipelineEvent::StageExited {
+                        index,
+                        status: (*status).into(),
+                    },
+                );
+            }
+            let stderr = join_reader(stderr_reader);
+            outputs.push(status.map(|status| StageOutput {
+                status,
+                stdout: Vec::new(),
+                stderr,
+            }));
+        }
+        let tail_index = self.piped_processes.len();
+        let tail_status = self.tail_process.take().unwrap().wait();
+        if let Ok(status) = &tail_status {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::StageExited {
+                    index: tail_index,
+                    status: (*status).into(),
+                },
+            );
         }
+        let tail_stdout = join_reader(tail_stdout_reader);
+        let tail_stderr = join_reader(tail_stderr_reader);
+        outputs.push(tail_status.map(|status| StageOutput {
+            status,
+            stdout: tail_stdout,
+            stderr: tail_stderr,
+        }));
+        // We've already waited on all the exit codes. No reason to do it again on Drop.
+        self.config.on_drop = OnDrop::Forget;
+
+        // `PipelineFinished` reports the same overall status `join` would have resolved to, so
+        // the event means the same thing regardless of which method produced it.
+        let final_status = if self.config.pipefail {
+            outputs
+                .iter()
+                .find(|result| !result.as_ref().is_ok_and(|output| output.status.success()))
+                .or_else(|| outputs.last())
+        } else {
+            outputs.last()
+        };
+        if let Some(Ok(output)) = final_status {
+            fire_event(
+                &self.config.on_event,
+                PipelineEvent::PipelineFinished {
+                    status: output.status.into(),
+                },
+            );
+        }
+        outputs
     }
 }
 
@@ -315,22 +738,197 @@ impl Drop for Pipeline {
         match self.config.on_drop {
             OnDrop::Forget => (),
             OnDrop::Wait => {
-                for process in &mut self.piped_processes {
-                    let _ = process.wait();
+                for (index, process) in self.piped_processes.iter_mut().enumerate() {
+                    if let Ok(status) = process.wait() {
+                        fire_event(
+                            &self.config.on_event,
+                            PipelineEvent::StageExited {
+                                index,
+                                status: status.into(),
+                            },
+                        );
+                    }
+                }
+                let tail_index = self.piped_processes.len();
+                if let Some(process) = self.tail_process.as_mut()
+                    && let Ok(status) = process.wait()
+                {
+                    fire_event(
+                        &self.config.on_event,
+                        PipelineEvent::StageExited {
+                            index: tail_index,
+                            status: status.into(),
+                        },
+                    );
                 }
-                let _ = self.tail_process.as_mut().map(|process| process.wait());
             }
             OnDrop::Kill => {
-                for process in &mut self.piped_processes {
+                for (index, process) in self.piped_processes.iter_mut().enumerate() {
                     let _ = process.kill();
-                    let _ = process.wait();
+                    if let Ok(status) = process.wait() {
+                        fire_event(
+                            &self.config.on_event,
+                            PipelineEvent::StageExited {
+                                index,
+                                status: status.into(),
+                            },
+                        );
+                    }
                 }
+                let tail_index = self.piped_processes.len();
                 if let Some(process) = self.tail_process.as_mut()
                     && let Ok(()) = process.kill()
+                    && let Ok(status) = process.wait()
                 {
-                    let _ = process.wait();
+                    fire_event(
+                        &self.config.on_event,
+                        PipelineEvent::StageExited {
+                            index: tail_index,
+                            status: status.into(),
+                        },
+                    );
                 }
             }
+            OnDrop::TerminateThen(timeout) => {
+                let tail_index = self.piped_processes.len();
+                let mut processes: Vec<(usize, &mut Child)> =
+                    self.piped_processes.iter_mut().enumerate().collect();
+                if let Some(tail) = self.tail_process.as_mut() {
+                    processes.push((tail_index, tail));
+                }
+                terminate_then_kill(processes, timeout, &self.config.on_event);
+            }
         }
     }
 }
+
+/// Sends a polite termination request to every process (`SIGTERM` on Unix, `kill` on Windows),
+/// polls them with `try_wait` until `timeout` elapses, then hard-`kill`s whichever are still
+/// alive. Polling rather than blocking on `wait` means a fast-exiting process doesn't cost the
+/// full timeout.
+fn terminate_then_kill(
+    mut processes: Vec<(usize, &mut Child)>,
+    timeout: Duration,
+    on_event: &Option<EventCallback>,
+) {
+    for (_, process) in &mut processes {
+        let _ = send_terminate(process);
+    }
+
+    let deadline = Instant::now() + timeout;
+    const POLL_INTERVAL: Duration = Duration::from_millis(20);
+    loop {
+        processes.retain_mut(|(index, process)| match process.try_wait() {
+            Ok(Some(status)) => {
+                fire_event(
+                    on_event,
+                    PipelineEvent::StageExited {
+                        index: *index,
+                        status: status.into(),
+                    },
+                );
+                false
+            }
+            _ => true,
+        });
+        if processes.is_empty() {
+            return;
+        }
+        let Some(remaining) = deadline.checked_duration_since(Instant::now()) else {
+            break;
+        };
+        std::thread::sleep(POLL_INTERVAL.min(remaining));
+    }
+
+    for (index, process) in processes {
+        let _ = process.kill();
+        if let Ok(status) = process.wait() {
+            fire_event(
+                on_event,
+                PipelineEvent::StageExited {
+                    index,
+                    status: status.into(),
+                },
+            );
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send_terminate(process: &mut Child) -> std::io::Result<()> {
+    nix::sys::signal::kill(
+        nix::unistd::Pid::from_raw(process.id() as libc::pid_t),
+        nix::sys::signal::Signal::SIGTERM,
+    )
+    .map_err(std::io::Error::from)
+}
+
+#[cfg(not(unix))]
+fn send_terminate(process: &mut Child) -> std::io::Result<()> {
+    process.kill()
+}
+
+/// Installs a `pre_exec` hook that joins `command`'s process to `leader_pid`'s group, or (when
+/// `leader_pid` is `None`) makes it the leader of a new group. Safe to call because `setpgid` is
+/// async-signal-safe, so it's sound to run between `fork` and `exec`.
+#[cfg(unix)]
+fn set_pgid_pre_exec(command: &mut Command, leader_pid: Option<libc::pid_t>) {
+    use std::os::unix::process::CommandExt;
+
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setpgid(0, leader_pid.unwrap_or(0)) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Installs a `pre_exec` hook that dups the child's stderr onto its stdout (`2>&1`), run after
+/// stdio has already been set up, so the hook sees stdout already pointed at the pipe we handed
+/// to the next stage.
+#[cfg(unix)]
+fn merge_stderr_into_stdout_pre_exec(command: &mut Command) {
+    use std::os::unix::process::CommandExt;
+
+    unsafe {
+        command.pre_exec(|| {
+            if libc::dup2(libc::STDOUT_FILENO, libc::STDERR_FILENO) == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+}
+
+#[cfg(unix)]
+impl Pipeline {
+    /// The process group id shared by every stage in the pipeline.
+    pub fn pgid(&self) -> libc::pid_t {
+        self.pgid
+    }
+
+    /// Sends `sig` to every process in the pipeline at once via `killpg`.
+    pub fn signal(&self, sig: libc::c_int) -> std::io::Result<()> {
+        if unsafe { libc::killpg(self.pgid, sig) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Suspends the whole pipeline (`SIGTSTP`), as if the user had pressed `Ctrl-Z` on it.
+    pub fn suspend(&self) -> std::io::Result<()> {
+        self.signal(libc::SIGTSTP)
+    }
+
+    /// Resumes a pipeline previously suspended with [`Pipeline::suspend`] (`SIGCONT`).
+    pub fn resume(&self) -> std::io::Result<()> {
+        self.signal(libc::SIGCONT)
+    }
+
+    /// Interrupts the whole pipeline (`SIGINT`), as if the user had pressed `Ctrl-C` on it.
+    pub fn interrupt(&self) -> std::io::Result<()> {
+        self.signal(libc::SIGINT)
+    }
+}
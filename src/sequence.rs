@@ -0,0 +1,73 @@
+//! Chains multiple [`CommandPipeline`]s together with shell-style `&&`/`||`/`;` control flow, so
+//! a caller can express e.g. `build | tee log && deploy || notify-failure` as one composable value
+//! instead of hand-rolling the status checks between each pipeline.
+
+use std::process::ExitStatus;
+
+use crate::CommandPipeline;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SequenceOp {
+    /// Run unconditionally (`;`).
+    Then,
+    /// Run only if the previous pipeline succeeded (`&&`).
+    AndThen,
+    /// Run only if the previous pipeline failed (`||`).
+    OrElse,
+}
+
+/// A sequence of [`CommandPipeline`]s joined by `&&`/`||`/`;` operators.
+#[derive(Debug)]
+pub struct PipelineSequence {
+    // Never empty. The first entry's `SequenceOp` is unused; it always runs.
+    steps: Vec<(SequenceOp, CommandPipeline)>,
+}
+
+impl PipelineSequence {
+    pub fn new(pipeline: CommandPipeline) -> Self {
+        Self {
+            steps: vec![(SequenceOp::Then, pipeline)],
+        }
+    }
+
+    /// Runs `pipeline` only if the previous pipeline's status was a success, i.e. shell's `&&`.
+    pub fn and_then(&mut self, pipeline: CommandPipeline) -> &mut Self {
+        self.steps.push((SequenceOp::AndThen, pipeline));
+        self
+    }
+
+    /// Runs `pipeline` only if the previous pipeline's status was not a success, i.e. shell's
+    /// `||`.
+    pub fn or_else(&mut self, pipeline: CommandPipeline) -> &mut Self {
+        self.steps.push((SequenceOp::OrElse, pipeline));
+        self
+    }
+
+    /// Always runs `pipeline` after the previous one, regardless of its status, i.e. shell's `;`.
+    pub fn then(&mut self, pipeline: CommandPipeline) -> &mut Self {
+        self.steps.push((SequenceOp::Then, pipeline));
+        self
+    }
+
+    /// Runs the sequence, evaluating each pipeline's status (respecting its own `pipefail`
+    /// setting) to decide whether the next one runs, and returning the status of the last
+    /// pipeline actually executed.
+    pub fn run(&mut self) -> std::io::Result<ExitStatus> {
+        let mut steps = self.steps.iter_mut();
+        let (_, first) = steps.next().expect("PipelineSequence is never empty");
+        let mut status = first.status()?;
+
+        for (op, pipeline) in steps {
+            let should_run = match op {
+                SequenceOp::Then => true,
+                SequenceOp::AndThen => status.success(),
+                SequenceOp::OrElse => !status.success(),
+            };
+            if should_run {
+                status = pipeline.status()?;
+            }
+        }
+
+        Ok(status)
+    }
+}
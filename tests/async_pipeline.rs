@@ -0,0 +1,37 @@
+//! Integration test for `AsyncCommandPipeline`/`AsyncPipeline`'s documented happy path:
+//! `spawn`/`join`/`join_with_output`, and `pipefail` resolving the overall status.
+
+use std::process::Stdio;
+
+use subprocess_pipeline::async_pipeline::AsyncCommandPipeline;
+
+#[tokio::test]
+async fn join_with_output_pipes_stages_together() {
+    let mut pipeline = AsyncCommandPipeline::new("printf");
+    pipeline.arg("hello");
+    pipeline.pipe("tr").arg("a-z").arg("A-Z");
+    pipeline.stdout(Stdio::piped());
+
+    let output = pipeline.output().await.expect("failed to run pipeline");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"HELLO");
+}
+
+#[tokio::test]
+async fn join_returns_tail_status_without_pipefail() {
+    let mut pipeline = AsyncCommandPipeline::new("false");
+    pipeline.pipe("true");
+
+    let status = pipeline.status().await.expect("failed to run pipeline");
+    assert!(status.success(), "without pipefail, only the tail's status matters");
+}
+
+#[tokio::test]
+async fn pipefail_surfaces_the_first_failing_stage() {
+    let mut pipeline = AsyncCommandPipeline::new("false");
+    pipeline.pipefail(true);
+    pipeline.pipe("true");
+
+    let status = pipeline.status().await.expect("failed to run pipeline");
+    assert!(!status.success(), "pipefail should surface the head stage's failure");
+}
@@ -0,0 +1,51 @@
+//! Integration test for `PipelineEvent`'s documented happy path: every stage fires
+//! `StageSpawned` then `StageExited` in order, followed by a single `PipelineFinished`.
+
+use std::sync::{Arc, Mutex};
+
+use subprocess_pipeline::{CommandPipeline, PipelineEvent};
+
+#[test]
+fn events_fire_in_order_with_expected_content() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_for_callback = Arc::clone(&events);
+
+    let mut pipeline = CommandPipeline::new("true");
+    pipeline.pipe("false");
+    pipeline.on_event(move |event| events_for_callback.lock().unwrap().push(event));
+
+    let status = pipeline
+        .spawn()
+        .expect("failed to spawn pipeline")
+        .join()
+        .expect("failed to join pipeline");
+    assert!(!status.success(), "tail stage (false) should fail");
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 5, "expected 2x StageSpawned + 2x StageExited + 1x PipelineFinished");
+
+    assert!(matches!(
+        events[0],
+        PipelineEvent::StageSpawned { index: 0, .. }
+    ));
+    assert!(matches!(
+        events[1],
+        PipelineEvent::StageSpawned { index: 1, .. }
+    ));
+
+    match &events[2] {
+        PipelineEvent::StageExited { index: 0, status } => assert!(status.success),
+        other => panic!("expected StageExited{{index: 0}}, got {other:?}"),
+    }
+    match &events[3] {
+        PipelineEvent::StageExited { index: 1, status } => assert!(!status.success),
+        other => panic!("expected StageExited{{index: 1}}, got {other:?}"),
+    }
+
+    match &events[4] {
+        PipelineEvent::PipelineFinished { status } => {
+            assert!(!status.success, "PipelineFinished should report the tail's status");
+        }
+        other => panic!("expected PipelineFinished, got {other:?}"),
+    }
+}
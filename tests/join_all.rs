@@ -0,0 +1,43 @@
+//! Integration test for `Pipeline::join_all`/`join_all_with_output`'s documented happy path:
+//! per-stage exit statuses (in pipeline order) and per-stage captured stderr.
+
+use subprocess_pipeline::CommandPipeline;
+
+#[test]
+fn join_all_reports_every_stage_status_in_order() {
+    let mut pipeline = CommandPipeline::new("false");
+    pipeline.pipe("true");
+
+    let statuses = pipeline.spawn().expect("failed to spawn pipeline").join_all();
+    assert_eq!(statuses.len(), 2);
+    assert!(!statuses[0].as_ref().unwrap().success(), "head stage (false) should fail");
+    assert!(statuses[1].as_ref().unwrap().success(), "tail stage (true) should succeed");
+}
+
+#[test]
+fn join_all_with_output_captures_each_stages_stderr() {
+    use std::process::Stdio;
+
+    let mut pipeline = CommandPipeline::new("sh");
+    pipeline
+        .arg("-c")
+        .arg("echo head-err >&2")
+        .stderr(Stdio::piped());
+    pipeline
+        .pipe("sh")
+        .arg("-c")
+        .arg("cat >/dev/null; echo tail-err >&2")
+        .stderr(Stdio::piped());
+
+    let outputs = pipeline
+        .spawn()
+        .expect("failed to spawn pipeline")
+        .join_all_with_output();
+    assert_eq!(outputs.len(), 2);
+
+    let head = outputs[0].as_ref().expect("head stage failed");
+    assert_eq!(head.stderr, b"head-err\n");
+
+    let tail = outputs[1].as_ref().expect("tail stage failed");
+    assert_eq!(tail.stderr, b"tail-err\n");
+}
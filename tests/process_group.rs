@@ -0,0 +1,99 @@
+//! Integration test for the Unix-only process-group controls added alongside job control
+//! support: every stage in a pipeline shares one process group, and `suspend`/`resume`/
+//! `interrupt` affect all of them at once via `killpg`.
+#![cfg(unix)]
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use std::os::unix::process::ExitStatusExt;
+
+use subprocess_pipeline::{CommandPipeline, PipelineEvent};
+
+/// The single-character process state from `/proc/<pid>/stat` (e.g. `S` sleeping, `T` stopped).
+/// `comm` (the second field) is parenthesized and may itself contain spaces or parens, so the
+/// state is read after the *last* `)` rather than by splitting on whitespace. Linux-only: `cfg(unix)`
+/// also covers macOS/*BSD, which don't have `/proc`.
+#[cfg(target_os = "linux")]
+fn proc_state(pid: u32) -> char {
+    let stat = std::fs::read_to_string(format!("/proc/{pid}/stat"))
+        .expect("failed to read /proc/<pid>/stat");
+    stat.rsplit_once(')')
+        .expect("unexpected /proc/<pid>/stat format")
+        .1
+        .trim_start()
+        .chars()
+        .next()
+        .expect("missing state field")
+}
+
+/// Whether `pid` is still alive, via a signal-0 `kill` probe. Used on non-Linux Unixes, where
+/// there's no portable way to distinguish "stopped" from "running" short of `/proc`.
+#[cfg(not(target_os = "linux"))]
+fn is_alive(pid: u32) -> bool {
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = std::time::Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(std::time::Instant::now() < deadline, "timed out waiting for condition");
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[test]
+fn suspend_resume_and_interrupt_affect_every_stage() {
+    let pids = Arc::new(Mutex::new(Vec::new()));
+    let pids_for_callback = Arc::clone(&pids);
+
+    let mut command = CommandPipeline::new("sleep");
+    command.arg("5").on_event(move |event| {
+        if let PipelineEvent::StageSpawned { pid, .. } = event {
+            pids_for_callback.lock().unwrap().push(pid);
+        }
+    });
+    command.pipe("sleep").arg("5");
+
+    let pipeline = command.spawn().expect("failed to spawn pipeline");
+    let pgid = pipeline.pgid();
+    assert!(pgid > 0);
+
+    let pids = pids.lock().unwrap().clone();
+    assert_eq!(pids.len(), 2, "expected a PipelineEvent::StageSpawned per stage");
+
+    // Every stage should be in the shared process group.
+    for &pid in &pids {
+        assert_eq!(unsafe { libc::getpgid(pid as libc::pid_t) }, pgid);
+    }
+
+    pipeline.suspend().expect("failed to suspend pipeline");
+    #[cfg(target_os = "linux")]
+    for &pid in &pids {
+        wait_until(|| proc_state(pid) == 'T');
+    }
+    #[cfg(not(target_os = "linux"))]
+    for &pid in &pids {
+        assert!(is_alive(pid), "stage should still be alive while stopped");
+    }
+
+    pipeline.resume().expect("failed to resume pipeline");
+    #[cfg(target_os = "linux")]
+    for &pid in &pids {
+        wait_until(|| proc_state(pid) != 'T');
+    }
+    #[cfg(not(target_os = "linux"))]
+    for &pid in &pids {
+        assert!(is_alive(pid), "stage should still be alive after resuming");
+    }
+
+    pipeline.interrupt().expect("failed to interrupt pipeline");
+    let statuses = pipeline.join_all();
+    for status in statuses {
+        let status = status.expect("failed to wait on stage");
+        assert_eq!(status.signal(), Some(libc::SIGINT));
+    }
+}
@@ -0,0 +1,85 @@
+//! Integration test for `PipelineSequence`'s documented happy path: `&&`/`||`/`;` short-circuiting.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use subprocess_pipeline::{sequence::PipelineSequence, CommandPipeline};
+
+/// A pipeline that touches a uniquely-named marker file, so later assertions can observe whether
+/// a step actually ran without needing to capture its output.
+fn touch_pipeline(marker: &Path) -> CommandPipeline {
+    let mut pipeline = CommandPipeline::new("sh");
+    pipeline.arg("-c").arg(format!("touch '{}'", marker.display()));
+    pipeline
+}
+
+struct MarkerDir(PathBuf);
+
+impl MarkerDir {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!(
+            "subprocess-pipeline-sequence-test-{name}-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).expect("failed to create marker dir");
+        Self(dir)
+    }
+
+    fn marker(&self, name: &str) -> PathBuf {
+        self.0.join(name)
+    }
+}
+
+impl Drop for MarkerDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[test]
+fn and_then_only_runs_after_success() {
+    let dir = MarkerDir::new("and-then");
+    let ran = dir.marker("ran");
+    let mut sequence = PipelineSequence::new(CommandPipeline::new("true"));
+    sequence.and_then(touch_pipeline(&ran));
+    let status = sequence.run().expect("sequence failed to run");
+    assert!(status.success());
+    assert!(ran.exists(), "and_then should run after a successful pipeline");
+
+    let dir = MarkerDir::new("and-then-skip");
+    let skipped = dir.marker("skipped");
+    let mut sequence = PipelineSequence::new(CommandPipeline::new("false"));
+    sequence.and_then(touch_pipeline(&skipped));
+    sequence.run().expect("sequence failed to run");
+    assert!(!skipped.exists(), "and_then should not run after a failing pipeline");
+}
+
+#[test]
+fn or_else_only_runs_after_failure() {
+    let dir = MarkerDir::new("or-else");
+    let ran = dir.marker("ran");
+    let mut sequence = PipelineSequence::new(CommandPipeline::new("false"));
+    sequence.or_else(touch_pipeline(&ran));
+    sequence.run().expect("sequence failed to run");
+    assert!(ran.exists(), "or_else should run after a failing pipeline");
+
+    let dir = MarkerDir::new("or-else-skip");
+    let skipped = dir.marker("skipped");
+    let mut sequence = PipelineSequence::new(CommandPipeline::new("true"));
+    sequence.or_else(touch_pipeline(&skipped));
+    sequence.run().expect("sequence failed to run");
+    assert!(!skipped.exists(), "or_else should not run after a successful pipeline");
+}
+
+#[test]
+fn then_always_runs_regardless_of_status() {
+    let dir = MarkerDir::new("then");
+    let ran = dir.marker("ran");
+    let mut sequence = PipelineSequence::new(CommandPipeline::new("false"));
+    sequence.then(touch_pipeline(&ran));
+    let status = sequence.run().expect("sequence failed to run");
+    assert!(status.success(), "run() should return the last-executed pipeline's status");
+    assert!(ran.exists(), "then should run regardless of the previous pipeline's status");
+}
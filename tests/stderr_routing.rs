@@ -0,0 +1,37 @@
+//! Integration test for `pipe_merged`/`redirect_stderr_to_next`'s documented happy path: routing
+//! a stage's stderr into the next stage's stdin, either merged with stdout or on its own.
+//! `pipe_merged` is Unix-only (see `CommandPipeline::pipe_merged`'s doc comment).
+
+use std::process::Stdio;
+
+use subprocess_pipeline::CommandPipeline;
+
+#[test]
+#[cfg(unix)]
+fn pipe_merged_interleaves_stderr_into_the_next_stage() {
+    let mut pipeline = CommandPipeline::new("sh");
+    pipeline.arg("-c").arg("echo out-line; echo err-line >&2");
+    pipeline.pipe_merged("cat");
+    pipeline.stdout(Stdio::piped());
+
+    let output = pipeline.output().expect("failed to run pipeline");
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("out-line"), "stdout: {stdout:?}");
+    assert!(stdout.contains("err-line"), "stdout: {stdout:?}");
+}
+
+#[test]
+fn redirect_stderr_to_next_sends_only_stderr() {
+    let mut pipeline = CommandPipeline::new("sh");
+    pipeline
+        .arg("-c")
+        .arg("echo out-line >/dev/null; echo err-line >&2");
+    pipeline.redirect_stderr_to_next();
+    pipeline.pipe("cat");
+    pipeline.stdout(Stdio::piped());
+
+    let output = pipeline.output().expect("failed to run pipeline");
+    assert!(output.status.success());
+    assert_eq!(output.stdout, b"err-line\n");
+}
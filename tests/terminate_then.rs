@@ -0,0 +1,74 @@
+//! Integration test for `OnDrop::TerminateThen`'s documented happy path: a polite SIGTERM,
+//! followed by a hard kill once the grace period elapses for processes that ignore it.
+#![cfg(unix)]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use subprocess_pipeline::{CommandPipeline, OnDrop, PipelineEvent};
+
+fn is_alive(pid: u32) -> bool {
+    unsafe { libc::kill(pid as libc::pid_t, 0) == 0 }
+}
+
+#[test]
+fn terminate_then_escalates_to_kill_after_the_grace_period() {
+    let pid = Arc::new(Mutex::new(None));
+    let pid_for_callback = Arc::clone(&pid);
+
+    // The marker file lets us wait for the trap to actually be installed before terminating the
+    // process, instead of guessing at a fixed delay and risking a flaky race against shell
+    // startup on a slow/contended machine.
+    let marker = std::env::temp_dir().join(format!(
+        "subprocess-pipeline-terminate-then-test-{}",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&marker);
+
+    let mut command = CommandPipeline::new("sh");
+    command
+        .arg("-c")
+        .arg(format!(
+            "trap '' TERM; touch '{}'; sleep 5",
+            marker.display()
+        ))
+        .on_drop(OnDrop::TerminateThen(Duration::from_millis(200)))
+        .on_event(move |event| {
+            if let PipelineEvent::StageSpawned { pid, .. } = event {
+                *pid_for_callback.lock().unwrap() = Some(pid);
+            }
+        });
+
+    let pipeline = command.spawn().expect("failed to spawn pipeline");
+    let pid = pid.lock().unwrap().expect("missing StageSpawned pid");
+    assert!(is_alive(pid), "the stage should be running right after spawn");
+
+    let wait_deadline = Instant::now() + Duration::from_secs(5);
+    while !marker.exists() {
+        assert!(
+            Instant::now() < wait_deadline,
+            "the shell never installed its trap"
+        );
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    let _ = std::fs::remove_file(&marker);
+
+    // Dropping blocks until the process is reaped (polite SIGTERM, ignored by the `trap`, then
+    // a hard kill once the timeout elapses), so by the time `drop` returns it must already be
+    // both past the grace period and gone.
+    let start = Instant::now();
+    drop(pipeline);
+    let elapsed = start.elapsed();
+
+    assert!(
+        elapsed >= Duration::from_millis(200),
+        "shouldn't escalate to kill before the configured grace period"
+    );
+    assert!(
+        elapsed < Duration::from_secs(5),
+        "escalation should happen well before the process would exit on its own"
+    );
+    assert!(!is_alive(pid), "the process should be reaped by the time Drop returns");
+}